@@ -0,0 +1,21 @@
+use leptos_router::ParamsError;
+use thiserror::Error;
+
+/// 应用级错误，展示在 `<Error/>` 页面中
+#[derive(Debug, Clone, Error)]
+pub enum AppError {
+    #[error("invalid game parameters: {0}")]
+    ParamsError(ParamsError),
+
+    #[error("not found")]
+    NotFound,
+}
+
+impl AppError {
+    pub fn status_code(&self) -> u16 {
+        match self {
+            AppError::ParamsError(_) => 400,
+            AppError::NotFound => 404,
+        }
+    }
+}
@@ -0,0 +1,932 @@
+use std::collections::BTreeSet;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use leptos::*;
+
+use crate::game_settings::Size;
+use crate::game_stats::Stats;
+
+/// 单元格对外展示用的状态，随 `set_cell_state` 信号推送给 `Cell` 组件
+pub type SetCellState = WriteSignal<(CellInteraction, CellKind)>;
+
+/// "Hint" 提示的结果，随 `set_highlight` 信号推送给 `Cell` 组件
+pub type SetHighlight = WriteSignal<Highlight>;
+
+/// 键盘光标是否落在这个格子上，随 `set_focused` 信号推送给 `Cell` 组件
+pub type SetFocused = WriteSignal<bool>;
+
+/// 逻辑推导给一个格子打上的提示标记
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    None,
+    Safe,
+    Mine,
+}
+
+/// 玩家对单元格做过的交互
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CellInteraction {
+    Untouched,
+    Cleared,
+    Flagged,
+}
+
+/// 单元格本身是什么（地雷，还是周围地雷数为 `n` 的空格）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CellKind {
+    Mine,
+    Clear(u8),
+}
+
+/// 从 URL 查询参数解析出的对局参数。`seed` 为空时随机生成，
+/// 带着 seed 的 URL 可以分享给另一个人在完全相同的棋盘上比赛
+#[derive(Debug, Clone, Copy, PartialEq, leptos_router::Params)]
+pub struct GameParams {
+    pub size: Size,
+    pub seed: Option<u64>,
+}
+
+/// 对局的进行状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum Status {
+    Playing,
+    Won,
+    Lost,
+}
+
+/// 展示给 `Info` 组件的只读数据
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameInfo {
+    pub flags_placed: isize,
+    pub mine_count: isize,
+    pub elapsed_secs: u32,
+    pub(crate) status: Status,
+}
+
+impl GameInfo {
+    pub fn to_view(&self) -> impl IntoView {
+        let suffix = match self.status {
+            Status::Playing => "",
+            Status::Won => " - You win!",
+            Status::Lost => " - Boom!",
+        };
+
+        format!(
+            "{}/{} flags - {}s{suffix}",
+            self.flags_placed, self.mine_count, self.elapsed_secs
+        )
+    }
+}
+
+struct Cell {
+    kind: CellKind,
+    interaction: CellInteraction,
+    setter: Option<SetCellState>,
+    highlight_setter: Option<SetHighlight>,
+    focus_setter: Option<SetFocused>,
+}
+
+impl Cell {
+    fn blank() -> Self {
+        Cell {
+            kind: CellKind::Clear(0),
+            interaction: CellInteraction::Untouched,
+            setter: None,
+            highlight_setter: None,
+            focus_setter: None,
+        }
+    }
+}
+
+/// 录制下来的一步操作，可导出为 JSON 并配合棋盘种子重放整局游戏
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Move {
+    pub row: isize,
+    pub column: isize,
+    pub interaction: CellInteraction,
+    pub elapsed_ms: u32,
+}
+
+/// 棋盘格子的可序列化快照，不含任何信号 —— 用来写入 `localStorage`
+/// 以及在 `GameState::load_snapshot` 中恢复一局未下完的游戏
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    rows: isize,
+    columns: isize,
+    mine_count: isize,
+    seed: u64,
+    mines_placed: bool,
+    flags_placed: isize,
+    revealed: isize,
+    status: Status,
+    elapsed_secs: u32,
+    cells: Vec<Vec<(CellKind, CellInteraction)>>,
+}
+
+impl GameSnapshot {
+    /// 存档对应的那局游戏当时是否还没分出胜负，
+    /// 用来决定要不要在 `pages::game::Game` 里提示"是否继续"
+    pub fn is_playing(&self) -> bool {
+        self.status == Status::Playing
+    }
+}
+
+/// 扫雷一局游戏的全部状态。存放在一个 `create_signal` 里，通过
+/// `game_state_write.update(..)` 驱动棋盘上每个格子各自的显示信号。
+pub struct GameState {
+    rows: isize,
+    columns: isize,
+    mine_count: isize,
+    size: Size,
+    seed: u64,
+    /// seed 是否是玩家通过 URL 指定的（分享出去的棋盘）。
+    /// 是的话 `reset` 要保留它，否则每次重开都得换一张新雷图
+    pinned_seed: bool,
+    cells: Vec<Vec<Cell>>,
+    mines_placed: bool,
+    flags_placed: isize,
+    revealed: isize,
+    status: RwSignal<Status>,
+    elapsed_secs: RwSignal<u32>,
+    cursor: (isize, isize),
+    moves: Vec<Move>,
+    /// 正在重放导入的操作序列时为 `true`：`dig`/`flag` 复用的是同一套逻辑，
+    /// 但这局胜负早就计过一次了，重放期间不应该再把统计和存档重复写一遍
+    replaying: bool,
+}
+
+impl GameState {
+    pub fn new(params: GameParams) -> Self {
+        let (rows, columns, mine_count) = params.size.dimensions();
+        let pinned_seed = params.seed.is_some();
+        let seed = params.seed.unwrap_or_else(rand::random);
+
+        GameState {
+            rows,
+            columns,
+            mine_count,
+            size: params.size,
+            seed,
+            pinned_seed,
+            cells: (0..rows).map(|_| (0..columns).map(|_| Cell::blank()).collect()).collect(),
+            mines_placed: false,
+            flags_placed: 0,
+            revealed: 0,
+            status: create_rw_signal(Status::Playing),
+            elapsed_secs: create_rw_signal(0),
+            cursor: (0, 0),
+            moves: Vec::new(),
+            replaying: false,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// 本局在 `localStorage` 中使用的键，由难度和种子唯一确定
+    pub fn storage_key(size: Size, seed: u64) -> String {
+        format!("minesweeper-save-{size}-{seed}")
+    }
+
+    fn to_snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            rows: self.rows,
+            columns: self.columns,
+            mine_count: self.mine_count,
+            seed: self.seed,
+            mines_placed: self.mines_placed,
+            flags_placed: self.flags_placed,
+            revealed: self.revealed,
+            status: self.status.get_untracked(),
+            elapsed_secs: self.elapsed_secs.get_untracked(),
+            cells: self
+                .cells
+                .iter()
+                .map(|row| row.iter().map(|cell| (cell.kind, cell.interaction)).collect())
+                .collect(),
+        }
+    }
+
+    /// 用快照覆盖当前棋盘，复用已经注册过的格子信号（与 `reset` 同理）
+    pub fn load_snapshot(&mut self, snapshot: GameSnapshot) {
+        self.mines_placed = snapshot.mines_placed;
+        self.flags_placed = snapshot.flags_placed;
+        self.revealed = snapshot.revealed;
+        self.status.set(snapshot.status);
+        self.elapsed_secs.set(snapshot.elapsed_secs);
+
+        for (row, saved_row) in self.cells.iter_mut().zip(snapshot.cells) {
+            for (cell, (kind, interaction)) in row.iter_mut().zip(saved_row) {
+                cell.kind = kind;
+                cell.interaction = interaction;
+
+                if let Some(setter) = cell.setter {
+                    setter.set((cell.interaction, cell.kind));
+                }
+            }
+        }
+    }
+
+    /// 把当前棋盘写入 `localStorage`，在每次 `dig`/`flag`/`chord` 之后调用。
+    /// 单元测试没有 `window`/`localStorage` 宿主，`cfg(test)` 下直接跳过
+    #[cfg(not(test))]
+    fn persist(&self) {
+        if self.replaying {
+            return;
+        }
+
+        let Some(storage) = window().local_storage().ok().flatten() else { return };
+        let Ok(json) = serde_json::to_string(&self.to_snapshot()) else { return };
+        let key = Self::storage_key(self.size(), self.seed);
+        let _ = storage.set_item(&key, &json);
+    }
+
+    #[cfg(test)]
+    fn persist(&self) {}
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// 已录制的操作序列，配合 `seed` 就能在另一局同样的棋盘上原样重放
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// 把已录制的操作序列导出为 JSON，可以分享给其他人重放
+    pub fn export_moves(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self.moves)
+    }
+
+    /// 从 JSON 还原一段操作序列，交给 `replay_move` 逐步重放
+    pub fn import_moves(json: &str) -> Result<Vec<Move>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn record_move(&mut self, row: isize, column: isize, interaction: CellInteraction) {
+        let elapsed_ms = self.elapsed_secs.get_untracked() * 1000;
+        self.moves.push(Move { row, column, interaction, elapsed_ms });
+    }
+
+    /// 重放模式下应用录制好的一步操作：按动作类型复用 `dig`/`flag` 本身，
+    /// 因为种子相同, 重新挖掘/插旗会驱动出和原局一模一样的格子信号
+    pub fn replay_move(&mut self, mv: Move) {
+        match mv.interaction {
+            CellInteraction::Cleared => self.dig(mv.row, mv.column),
+            CellInteraction::Flagged | CellInteraction::Untouched => self.flag(mv.row, mv.column),
+        }
+    }
+
+    /// 导入一段回放前调用：保留当前 seed 清空棋盘重新开始，并标记为"重放中"，
+    /// 这样逐步重放触发的 `dig`/`flag` 不会把这局早就计过的胜负再统计一遍
+    pub fn start_replay(&mut self) {
+        let seed = self.seed;
+        self.reset();
+        self.seed = seed;
+        self.replaying = true;
+    }
+
+    /// 录制的操作序列播放/单步完毕时调用：回放结束后棋盘恢复正常交互，
+    /// 接下来真正下出的每一步都要重新计入存档和统计
+    pub fn finish_replay(&mut self) {
+        self.replaying = false;
+    }
+
+    pub fn dimensions(&self) -> (isize, isize) {
+        (self.rows, self.columns)
+    }
+
+    /// 棋盘重新开始一局，复用已经注册过的格子信号。
+    /// seed 没有被玩家通过 URL 固定住的话，这里要换一个新 seed，
+    /// 否则 `place_mines` 的打乱顺序不变，每次重开都会摆出同样的雷图
+    pub fn reset(&mut self) {
+        if !self.pinned_seed {
+            self.seed = rand::random();
+        }
+
+        self.mines_placed = false;
+        self.flags_placed = 0;
+        self.revealed = 0;
+        self.status.set(Status::Playing);
+        self.elapsed_secs.set(0);
+        self.moves.clear();
+        self.replaying = false;
+
+        for row in self.cells.iter_mut() {
+            for cell in row.iter_mut() {
+                cell.kind = CellKind::Clear(0);
+                cell.interaction = CellInteraction::Untouched;
+
+                if let Some(setter) = cell.setter {
+                    setter.set((cell.interaction, cell.kind));
+                }
+            }
+        }
+
+        self.clear_highlights();
+    }
+
+    pub fn register_cell(&mut self, row: isize, column: isize, setter: SetCellState) {
+        let cell = &mut self.cells[row as usize][column as usize];
+        cell.setter = Some(setter);
+        setter.set((cell.interaction, cell.kind));
+    }
+
+    pub fn register_highlight(&mut self, row: isize, column: isize, setter: SetHighlight) {
+        self.cells[row as usize][column as usize].highlight_setter = Some(setter);
+    }
+
+    pub fn register_focus(&mut self, row: isize, column: isize, setter: SetFocused) {
+        let cell = &mut self.cells[row as usize][column as usize];
+        cell.focus_setter = Some(setter);
+        setter.set((row, column) == self.cursor);
+    }
+
+    fn clear_highlights(&mut self) {
+        for row in self.cells.iter_mut() {
+            for cell in row.iter_mut() {
+                if let Some(setter) = cell.highlight_setter {
+                    setter.set(Highlight::None);
+                }
+            }
+        }
+    }
+
+    /// 玩家是否允许开始新的一局：只有分出胜负后才为 `true`，进行中为 `false`
+    pub fn new_game_enabled_signal(&self) -> Memo<bool> {
+        let status = self.status;
+        create_memo(move |_| status.get() != Status::Playing)
+    }
+
+    /// 每秒被 `Game` 组件的计时器调用一次；游戏结束后是空操作
+    pub fn tick(&mut self) {
+        if self.status.get_untracked() != Status::Playing {
+            return;
+        }
+
+        self.elapsed_secs.update(|secs| *secs += 1);
+    }
+
+    pub fn info_signal(&self) -> Memo<GameInfo> {
+        let status = self.status;
+        let elapsed_secs = self.elapsed_secs;
+        let flags_placed = self.flags_placed;
+        let mine_count = self.mine_count;
+
+        create_memo(move |_| GameInfo {
+            flags_placed,
+            mine_count,
+            elapsed_secs: elapsed_secs.get(),
+            status: status.get(),
+        })
+    }
+
+    fn push_focus(&self, row: isize, column: isize, focused: bool) {
+        if let Some(setter) = self.cell(row, column).focus_setter {
+            setter.set(focused);
+        }
+    }
+
+    /// 键盘光标移动，按方向键/WASD 调用，四周 clamp 到棋盘范围内
+    pub fn move_cursor(&mut self, dr: isize, dc: isize) {
+        if self.status.get_untracked() != Status::Playing {
+            return;
+        }
+
+        let (row, column) = self.cursor;
+        let new_row = (row + dr).clamp(0, self.rows - 1);
+        let new_column = (column + dc).clamp(0, self.columns - 1);
+
+        if (new_row, new_column) == self.cursor {
+            return;
+        }
+
+        self.push_focus(row, column, false);
+        self.cursor = (new_row, new_column);
+        self.push_focus(new_row, new_column, true);
+    }
+
+    /// Space/Enter: 挖掘光标所在的格子
+    pub fn dig_cursor(&mut self) {
+        let (row, column) = self.cursor;
+        self.dig(row, column);
+    }
+
+    /// F: 给光标所在的格子插旗
+    pub fn flag_cursor(&mut self) {
+        let (row, column) = self.cursor;
+        self.flag(row, column);
+    }
+
+    fn in_bounds(&self, row: isize, column: isize) -> bool {
+        row >= 0 && row < self.rows && column >= 0 && column < self.columns
+    }
+
+    /// 一个格子的八邻域（Game of Life 里用的同一种扫描方式）
+    fn neighbors(&self, row: isize, column: isize) -> Vec<(isize, isize)> {
+        (-1..=1)
+            .flat_map(|dr| (-1..=1).map(move |dc| (dr, dc)))
+            .filter(|&(dr, dc)| (dr, dc) != (0, 0))
+            .map(|(dr, dc)| (row + dr, column + dc))
+            .filter(|&(r, c)| self.in_bounds(r, c))
+            .collect()
+    }
+
+    fn cell(&self, row: isize, column: isize) -> &Cell {
+        &self.cells[row as usize][column as usize]
+    }
+
+    fn push_cell_state(&self, row: isize, column: isize) {
+        let cell = self.cell(row, column);
+
+        if let Some(setter) = cell.setter {
+            setter.set((cell.interaction, cell.kind));
+        }
+
+        if let Some(setter) = cell.highlight_setter {
+            setter.set(Highlight::None);
+        }
+    }
+
+    /// 第一次挖掘时才真正布雷，保证玩家的第一下点击及其周围都是安全的。
+    /// 打乱顺序只由 `seed` 决定、与点开的格子无关，落在安全区里的雷会被挪到
+    /// 打乱顺序中下一个空位。同一个 seed 只有在打乱后的前 `mine_count` 个格子
+    /// 都不落在安全区里时，才与先点哪里无关；否则雷图随第一下点击的位置而不同
+    fn place_mines(&mut self, safe_row: isize, safe_col: isize) {
+        let safe_zone: Vec<(isize, isize)> = self
+            .neighbors(safe_row, safe_col)
+            .into_iter()
+            .chain(std::iter::once((safe_row, safe_col)))
+            .collect();
+
+        let mut order: Vec<(isize, isize)> = (0..self.rows)
+            .flat_map(|row| (0..self.columns).map(move |column| (row, column)))
+            .collect();
+
+        order.shuffle(&mut StdRng::seed_from_u64(self.seed));
+
+        let mut mines: Vec<(isize, isize)> = order.iter().take(self.mine_count as usize).copied().collect();
+        let mut reserve = order.into_iter().skip(self.mine_count as usize);
+
+        for i in 0..mines.len() {
+            if !safe_zone.contains(&mines[i]) {
+                continue;
+            }
+
+            loop {
+                let candidate = reserve.next().expect("board has room for all mines outside the safe zone");
+
+                if !safe_zone.contains(&candidate) && !mines.contains(&candidate) {
+                    mines[i] = candidate;
+                    break;
+                }
+            }
+        }
+
+        for &(row, column) in &mines {
+            self.cells[row as usize][column as usize].kind = CellKind::Mine;
+        }
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                if self.cells[row as usize][column as usize].kind == CellKind::Mine {
+                    continue;
+                }
+
+                let adjacent = self
+                    .neighbors(row, column)
+                    .iter()
+                    .filter(|&&(r, c)| self.cells[r as usize][c as usize].kind == CellKind::Mine)
+                    .count();
+
+                self.cells[row as usize][column as usize].kind = CellKind::Clear(adjacent as u8);
+            }
+        }
+
+        self.mines_placed = true;
+    }
+
+    pub fn dig(&mut self, row: isize, column: isize) {
+        if self.status.get_untracked() != Status::Playing {
+            return;
+        }
+
+        if self.cell(row, column).interaction != CellInteraction::Untouched {
+            return;
+        }
+
+        if !self.mines_placed {
+            self.place_mines(row, column);
+        }
+
+        self.reveal(row, column);
+        self.record_move(row, column, CellInteraction::Cleared);
+        self.check_win();
+        self.persist();
+    }
+
+    /// 对一个已挖开的数字格中周数进行"连锁挖掘"：若旗帜数已满足数字，
+    /// 自动挖掘其余未标记的邻居
+    pub fn chord(&mut self, row: isize, column: isize) {
+        if self.status.get_untracked() != Status::Playing {
+            return;
+        }
+
+        let cell = self.cell(row, column);
+
+        let CellKind::Clear(n) = cell.kind else { return };
+        if cell.interaction != CellInteraction::Cleared {
+            return;
+        }
+
+        let neighbors = self.neighbors(row, column);
+
+        let flagged = neighbors
+            .iter()
+            .filter(|&&(r, c)| self.cell(r, c).interaction == CellInteraction::Flagged)
+            .count();
+
+        if flagged != n as usize {
+            return;
+        }
+
+        for (r, c) in neighbors {
+            // 挖到雷就和手动挖掘一样结束, 剩下的邻居不再挖开
+            if self.status.get_untracked() != Status::Playing {
+                break;
+            }
+
+            if self.cell(r, c).interaction == CellInteraction::Untouched {
+                self.reveal(r, c);
+                self.record_move(r, c, CellInteraction::Cleared);
+            }
+        }
+
+        self.check_win();
+        self.persist();
+    }
+
+    /// 挖开一个格子，数字为 0 时像 Game of Life 那样向外扩散（洪泛填充）
+    fn reveal(&mut self, row: isize, column: isize) {
+        let cell = &mut self.cells[row as usize][column as usize];
+
+        if cell.interaction != CellInteraction::Untouched {
+            return;
+        }
+
+        cell.interaction = CellInteraction::Cleared;
+        let kind = cell.kind;
+        self.push_cell_state(row, column);
+
+        match kind {
+            CellKind::Mine => {
+                self.status.set(Status::Lost);
+                self.reveal_all_mines();
+                self.record_stats(false);
+            }
+            CellKind::Clear(0) => {
+                self.revealed += 1;
+
+                for (r, c) in self.neighbors(row, column) {
+                    self.reveal(r, c);
+                }
+            }
+            CellKind::Clear(_) => {
+                self.revealed += 1;
+            }
+        }
+    }
+
+    fn reveal_all_mines(&mut self) {
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let cell = &mut self.cells[row as usize][column as usize];
+
+                if cell.kind == CellKind::Mine && cell.interaction == CellInteraction::Untouched {
+                    cell.interaction = CellInteraction::Cleared;
+                    self.push_cell_state(row, column);
+                }
+            }
+        }
+    }
+
+    fn check_win(&mut self) {
+        let safe_cells = self.rows * self.columns - self.mine_count;
+
+        if self.status.get_untracked() == Status::Playing && self.revealed >= safe_cells {
+            self.status.set(Status::Won);
+            self.record_stats(true);
+        }
+    }
+
+    /// 对局分出胜负时累积到对应难度档位的统计里
+    fn record_stats(&self, won: bool) {
+        if self.replaying {
+            return;
+        }
+
+        Stats::record_result(self.size(), won, self.elapsed_secs.get_untracked());
+    }
+
+    pub fn flag(&mut self, row: isize, column: isize) {
+        if self.status.get_untracked() != Status::Playing {
+            return;
+        }
+
+        let cell = &mut self.cells[row as usize][column as usize];
+
+        let interaction = match cell.interaction {
+            CellInteraction::Untouched => {
+                self.flags_placed += 1;
+                CellInteraction::Flagged
+            }
+            CellInteraction::Flagged => {
+                self.flags_placed -= 1;
+                CellInteraction::Untouched
+            }
+            CellInteraction::Cleared => return,
+        };
+
+        cell.interaction = interaction;
+
+        self.push_cell_state(row, column);
+        self.record_move(row, column, interaction);
+        self.persist();
+    }
+
+    /// 由每个已揭开的数字格建立约束 `sum(未知邻居) = n - 已插旗邻居`，
+    /// 反复应用单点规则与子集规则直到不动点，得出可以确定安全或确定为雷的格子
+    pub fn deduce(&self) -> (Vec<(isize, isize)>, Vec<(isize, isize)>) {
+        let mut constraints: Vec<(BTreeSet<(isize, isize)>, isize)> = Vec::new();
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let CellKind::Clear(n) = self.cell(row, column).kind else { continue };
+                if self.cell(row, column).interaction != CellInteraction::Cleared {
+                    continue;
+                }
+
+                let neighbors = self.neighbors(row, column);
+
+                let covered: BTreeSet<(isize, isize)> = neighbors
+                    .iter()
+                    .filter(|&&(r, c)| self.cell(r, c).interaction == CellInteraction::Untouched)
+                    .copied()
+                    .collect();
+
+                let flagged = neighbors
+                    .iter()
+                    .filter(|&&(r, c)| self.cell(r, c).interaction == CellInteraction::Flagged)
+                    .count();
+
+                if !covered.is_empty() {
+                    constraints.push((covered, n as isize - flagged as isize));
+                }
+            }
+        }
+
+        let mut safe = BTreeSet::new();
+        let mut mines = BTreeSet::new();
+
+        loop {
+            let mut changed = false;
+
+            // 单点规则: v == 0 全部安全, v == |S| 全部是雷
+            for (cells, value) in &constraints {
+                if *value == 0 {
+                    changed |= cells.iter().any(|cell| safe.insert(*cell));
+                } else if *value == cells.len() as isize {
+                    changed |= cells.iter().any(|cell| mines.insert(*cell));
+                }
+            }
+
+            // 把已判定的格子从所有约束里移除，地雷要相应扣减约束值
+            for (cells, value) in constraints.iter_mut() {
+                let removed_mines = cells.intersection(&mines).count() as isize;
+                cells.retain(|cell| !safe.contains(cell) && !mines.contains(cell));
+                *value -= removed_mines;
+            }
+            constraints.retain(|(cells, _)| !cells.is_empty());
+
+            // 子集规则: S1 ⊂ S2 时, (S2 \ S1, v2 - v1) 也是一条约束
+            let mut derived = Vec::new();
+
+            for (cells_a, value_a) in &constraints {
+                for (cells_b, value_b) in &constraints {
+                    if cells_a.len() < cells_b.len() && cells_a.is_subset(cells_b) {
+                        let diff: BTreeSet<_> = cells_b.difference(cells_a).copied().collect();
+                        derived.push((diff, value_b - value_a));
+                    }
+                }
+            }
+
+            for derived_constraint in derived {
+                if !constraints.contains(&derived_constraint) {
+                    constraints.push(derived_constraint);
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        (safe.into_iter().collect(), mines.into_iter().collect())
+    }
+
+    /// "Hint" 按钮: 运行 `deduce` 并把结果推送到各格子的高亮信号上
+    pub fn hint(&mut self) {
+        if self.status.get_untracked() != Status::Playing {
+            return;
+        }
+
+        let (safe, mines) = self.deduce();
+        self.clear_highlights();
+
+        for (row, column) in safe {
+            if let Some(setter) = self.cell(row, column).highlight_setter {
+                setter.set(Highlight::Safe);
+            }
+        }
+
+        for (row, column) in mines {
+            if let Some(setter) = self.cell(row, column).highlight_setter {
+                setter.set(Highlight::Mine);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造一个所有格子都是空白/未挖开的棋盘，交给各测试手动摆放格子
+    fn blank_state(rows: isize, columns: isize, mine_count: isize) -> GameState {
+        let _runtime = create_runtime();
+
+        GameState {
+            rows,
+            columns,
+            mine_count,
+            size: Size::Small,
+            seed: 0,
+            pinned_seed: true,
+            cells: (0..rows).map(|_| (0..columns).map(|_| Cell::blank()).collect()).collect(),
+            mines_placed: true,
+            flags_placed: 0,
+            revealed: 0,
+            status: create_rw_signal(Status::Playing),
+            elapsed_secs: create_rw_signal(0),
+            cursor: (0, 0),
+            moves: Vec::new(),
+            replaying: false,
+        }
+    }
+
+    fn set_cell(state: &mut GameState, row: isize, column: isize, kind: CellKind, interaction: CellInteraction) {
+        let cell = &mut state.cells[row as usize][column as usize];
+        cell.kind = kind;
+        cell.interaction = interaction;
+    }
+
+    fn mine_positions(state: &GameState) -> Vec<(isize, isize)> {
+        let mut positions = Vec::new();
+
+        for row in 0..state.rows {
+            for column in 0..state.columns {
+                if state.cells[row as usize][column as usize].kind == CellKind::Mine {
+                    positions.push((row, column));
+                }
+            }
+        }
+
+        positions
+    }
+
+    // 2x2 棋盘, 左上角是雷, 其余三格都已挖开且数字都是 1
+    // (它们唯一的未知邻居就是那个雷) -> 单点规则应该判定它是雷
+    #[test]
+    fn deduce_single_point_rule_finds_mine() {
+        let mut state = blank_state(2, 2, 1);
+
+        set_cell(&mut state, 0, 0, CellKind::Mine, CellInteraction::Untouched);
+        set_cell(&mut state, 0, 1, CellKind::Clear(1), CellInteraction::Cleared);
+        set_cell(&mut state, 1, 0, CellKind::Clear(1), CellInteraction::Cleared);
+        set_cell(&mut state, 1, 1, CellKind::Clear(1), CellInteraction::Cleared);
+
+        let (safe, mines) = state.deduce();
+
+        assert!(safe.is_empty());
+        assert_eq!(mines, vec![(0, 0)]);
+    }
+
+    // 左上角数字是 0 (没有雷), 它周围未知的邻居应该全部判定为安全
+    #[test]
+    fn deduce_single_point_rule_finds_safe_cells() {
+        let mut state = blank_state(2, 2, 0);
+
+        set_cell(&mut state, 0, 0, CellKind::Clear(0), CellInteraction::Cleared);
+
+        let (mut safe, mines) = state.deduce();
+        safe.sort();
+
+        assert_eq!(safe, vec![(0, 1), (1, 0), (1, 1)]);
+        assert!(mines.is_empty());
+    }
+
+    // row0: 1 2 1 (已挖开), row1: 三个未知格子, 共 2 个雷
+    // 两侧的 "1" 各自的未知邻居是中间 "2" 未知邻居的子集, 子集规则先推出
+    // 两端各是一个雷, 再把它们从中间的约束里扣掉后单点规则判定中间那格安全
+    #[test]
+    fn deduce_subset_rule_cascades_to_single_point() {
+        let mut state = blank_state(2, 3, 2);
+
+        set_cell(&mut state, 0, 0, CellKind::Clear(1), CellInteraction::Cleared);
+        set_cell(&mut state, 0, 1, CellKind::Clear(2), CellInteraction::Cleared);
+        set_cell(&mut state, 0, 2, CellKind::Clear(1), CellInteraction::Cleared);
+
+        let (safe, mut mines) = state.deduce();
+        mines.sort();
+
+        assert_eq!(safe, vec![(1, 1)]);
+        assert_eq!(mines, vec![(1, 0), (1, 2)]);
+    }
+
+    // 旗帜数刚好等于数字时, chord 应该自动挖开其余未标记的邻居
+    #[test]
+    fn chord_digs_remaining_neighbors_when_flags_satisfy_number() {
+        let mut state = blank_state(2, 2, 1);
+
+        set_cell(&mut state, 0, 0, CellKind::Clear(1), CellInteraction::Cleared);
+        set_cell(&mut state, 0, 1, CellKind::Mine, CellInteraction::Flagged);
+        set_cell(&mut state, 1, 0, CellKind::Clear(1), CellInteraction::Untouched);
+        set_cell(&mut state, 1, 1, CellKind::Clear(1), CellInteraction::Untouched);
+        state.flags_placed = 1;
+
+        state.chord(0, 0);
+
+        assert_eq!(state.cells[1][0].interaction, CellInteraction::Cleared);
+        assert_eq!(state.cells[1][1].interaction, CellInteraction::Cleared);
+
+        let mut revealed: Vec<(isize, isize)> = state.moves().iter().map(|mv| (mv.row, mv.column)).collect();
+        revealed.sort();
+
+        assert_eq!(revealed, vec![(1, 0), (1, 1)]);
+    }
+
+    // 旗帜数和数字不一致时, chord 必须是 no-op
+    #[test]
+    fn chord_is_noop_when_flag_count_does_not_match() {
+        let mut state = blank_state(2, 2, 1);
+
+        set_cell(&mut state, 0, 0, CellKind::Clear(1), CellInteraction::Cleared);
+        set_cell(&mut state, 0, 1, CellKind::Mine, CellInteraction::Untouched);
+        set_cell(&mut state, 1, 0, CellKind::Clear(1), CellInteraction::Untouched);
+        set_cell(&mut state, 1, 1, CellKind::Clear(1), CellInteraction::Untouched);
+
+        state.chord(0, 0);
+
+        assert_eq!(state.cells[1][0].interaction, CellInteraction::Untouched);
+        assert_eq!(state.cells[1][1].interaction, CellInteraction::Untouched);
+        assert!(state.moves().is_empty());
+    }
+
+    // 同一个 seed 不管先点哪个格子都必须得到同一张雷图，否则分享 seed 就没有意义
+    #[test]
+    fn place_mines_layout_depends_only_on_seed_not_first_click() {
+        let mut clicked_top_left = blank_state(9, 9, 10);
+        clicked_top_left.seed = 42;
+        clicked_top_left.place_mines(0, 0);
+
+        let mut clicked_bottom_right = blank_state(9, 9, 10);
+        clicked_bottom_right.seed = 42;
+        clicked_bottom_right.place_mines(8, 8);
+
+        assert_eq!(mine_positions(&clicked_top_left), mine_positions(&clicked_bottom_right));
+    }
+
+    // 安全区以外还有足够的候选格，所以重定位后也不应该把雷放进安全区里
+    #[test]
+    fn place_mines_never_puts_a_mine_in_the_safe_zone() {
+        let mut state = blank_state(9, 9, 10);
+        state.seed = 7;
+        state.place_mines(4, 4);
+
+        let safe_zone: Vec<(isize, isize)> = state
+            .neighbors(4, 4)
+            .into_iter()
+            .chain(std::iter::once((4, 4)))
+            .collect();
+
+        for mine in mine_positions(&state) {
+            assert!(!safe_zone.contains(&mine));
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// 棋盘难度档位（行数、列数、地雷数由档位决定）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Size {
+    Small,
+    Medium,
+    Large,
+}
+
+impl Size {
+    /// 返回 (行数, 列数, 地雷数)
+    pub fn dimensions(&self) -> (isize, isize, isize) {
+        match self {
+            Size::Small => (9, 9, 10),
+            Size::Medium => (16, 16, 40),
+            Size::Large => (16, 30, 99),
+        }
+    }
+}
+
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Size::Small => "small",
+            Size::Medium => "medium",
+            Size::Large => "large",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Size {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "small" => Ok(Size::Small),
+            "medium" => Ok(Size::Medium),
+            "large" => Ok(Size::Large),
+            other => Err(format!("unknown size: {other}")),
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game_settings::Size;
+
+/// 单个难度档位下累积的战绩
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct DifficultyStats {
+    pub games_played: u32,
+    pub games_won: u32,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub best_time_secs: Option<u32>,
+}
+
+impl DifficultyStats {
+    fn record(&mut self, won: bool, elapsed_secs: u32) {
+        self.games_played += 1;
+
+        if won {
+            self.games_won += 1;
+            self.current_streak += 1;
+            self.longest_streak = self.longest_streak.max(self.current_streak);
+            self.best_time_secs = Some(match self.best_time_secs {
+                Some(best) => best.min(elapsed_secs),
+                None => elapsed_secs,
+            });
+        } else {
+            self.current_streak = 0;
+        }
+    }
+
+    /// 胜率，范围 `[0, 1]`
+    pub fn win_rate(&self) -> f64 {
+        if self.games_played == 0 {
+            0.0
+        } else {
+            self.games_won as f64 / self.games_played as f64
+        }
+    }
+}
+
+/// 所有难度档位的战绩，以 `Size` 的展示名作为键，持久化到 `localStorage`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Stats {
+    by_difficulty: BTreeMap<String, DifficultyStats>,
+}
+
+impl Stats {
+    const STORAGE_KEY: &'static str = "minesweeper-stats";
+
+    /// 从 `localStorage` 读取已保存的战绩，没有存档时返回空的默认值
+    pub fn load() -> Self {
+        window()
+            .local_storage()
+            .ok()
+            .flatten()
+            .and_then(|storage| storage.get_item(Self::STORAGE_KEY).ok().flatten())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        let Some(storage) = window().local_storage().ok().flatten() else { return };
+        let Ok(json) = serde_json::to_string(self) else { return };
+        let _ = storage.set_item(Self::STORAGE_KEY, &json);
+    }
+
+    /// 某个难度档位目前的战绩，从未玩过则是全零的默认值
+    pub fn for_difficulty(&self, size: Size) -> DifficultyStats {
+        self.by_difficulty.get(&size.to_string()).copied().unwrap_or_default()
+    }
+
+    /// 一局游戏分出胜负时调用一次：累积到对应难度档位并立即持久化
+    pub fn record_result(size: Size, won: bool, elapsed_secs: u32) {
+        let mut stats = Self::load();
+        stats.by_difficulty.entry(size.to_string()).or_default().record(won, elapsed_secs);
+        stats.save();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn win_rate_is_zero_with_no_games_played() {
+        assert_eq!(DifficultyStats::default().win_rate(), 0.0);
+    }
+
+    #[test]
+    fn record_tracks_games_played_and_won() {
+        let mut stats = DifficultyStats::default();
+
+        stats.record(true, 10);
+        stats.record(false, 20);
+        stats.record(true, 30);
+
+        assert_eq!(stats.games_played, 3);
+        assert_eq!(stats.games_won, 2);
+        assert_eq!(stats.win_rate(), 2.0 / 3.0);
+    }
+
+    #[test]
+    fn record_tracks_best_time_across_wins_only() {
+        let mut stats = DifficultyStats::default();
+
+        stats.record(true, 50);
+        stats.record(false, 5); // 输了的这局不应该影响最佳用时
+        stats.record(true, 30);
+        stats.record(true, 40);
+
+        assert_eq!(stats.best_time_secs, Some(30));
+    }
+
+    #[test]
+    fn record_tracks_current_and_longest_streak() {
+        let mut stats = DifficultyStats::default();
+
+        stats.record(true, 10);
+        stats.record(true, 10);
+        assert_eq!(stats.current_streak, 2);
+        assert_eq!(stats.longest_streak, 2);
+
+        stats.record(false, 10);
+        assert_eq!(stats.current_streak, 0);
+        assert_eq!(stats.longest_streak, 2);
+
+        stats.record(true, 10);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.longest_streak, 2);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod app_error;
+pub mod game_logic;
+pub mod game_settings;
+pub mod game_stats;
+pub mod pages;
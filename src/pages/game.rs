@@ -1,11 +1,29 @@
 use leptos::*;
 use leptos_router::*;
+use wasm_bindgen::JsValue;
 
 use crate::app_error::AppError;
-use crate::game_logic::{CellInteraction, CellKind, GameParams, GameState};
+use crate::game_logic::{CellInteraction, CellKind, GameParams, GameSnapshot, GameState, Highlight, Move};
 use crate::game_settings::Size;
 use crate::pages::Error;
 
+/// 读取之前为同一难度/种子保存在 `localStorage` 里的存档（如果有）
+fn load_saved_game(size: Size, seed: u64) -> Option<GameSnapshot> {
+    let storage = window().local_storage().ok().flatten()?;
+    let json = storage.get_item(&GameState::storage_key(size, seed)).ok().flatten()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// 把生成出来的 seed 写回地址栏 (`history.replaceState`，不触发导航/刷新)，
+/// 这样玩家复制当前地址分享出去，对方打开的就是同一张雷图
+fn sync_seed_to_url(size: Size, seed: u64) {
+    let Ok(history) = window().history() else { return };
+    let Ok(pathname) = window().location().pathname() else { return };
+
+    let url = format!("{pathname}?size={size}&seed={seed}");
+    let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&url));
+}
+
 // 定义用于显示数字的SVG图标
 const NUM_SVGS: [&str; 9] = [
     "", // 索引从1开始
@@ -29,7 +47,26 @@ pub fn Game() -> impl IntoView {
 
     use_query::<GameParams>().with_untracked(|params| match params {
         Ok(params) => {
-            let game_state = GameState::new(*params);
+            // seed 没固定时由 GameState::new 随机生成一个, 并由它记住 seed 有没有固定:
+            // 没固定的话每次 New Game 都会再换一个新 seed
+            let mut game_state = GameState::new(*params);
+            let seed = game_state.seed();
+
+            // 把(可能是刚生成的)seed 同步进地址栏, 保证分享当前 URL 总能复现同一张雷图
+            sync_seed_to_url(params.size, seed);
+
+            // 只有存档还在进行中才提示恢复; 已经分出胜负的存档直接无视,
+            // 否则同一个 seed 每次打开都会被问"是否继续"一局早就结束的游戏
+            if let Some(snapshot) = load_saved_game(params.size, seed).filter(|snapshot| snapshot.is_playing()) {
+                let resume = window()
+                    .confirm_with_message("发现一局未完成的游戏, 是否继续?")
+                    .unwrap_or(false);
+
+                if resume {
+                    game_state.load_snapshot(snapshot);
+                }
+            }
+
             let (rows, columns) = game_state.dimensions();
             let new_game_enabled = game_state.new_game_enabled_signal();
 
@@ -37,6 +74,76 @@ pub fn Game() -> impl IntoView {
             provide_context(game_state_read);
             provide_context(game_state_write);
 
+            // 计时器: 每秒推进一次 elapsed_secs, 游戏结束后 tick() 自己是空操作
+            let _ = set_interval(
+                move || game_state_write.update(|game_state| game_state.tick()),
+                std::time::Duration::from_secs(1),
+            );
+
+            // 键盘操作: 方向键/WASD 移动光标, Space/Enter 挖掘, F 插旗
+            window_event_listener(ev::keydown, move |ev| {
+                if new_game_enabled.get_untracked() {
+                    return;
+                }
+
+                match ev.key().as_str() {
+                    "ArrowUp" | "w" | "W" => game_state_write.update(|game_state| game_state.move_cursor(-1, 0)),
+                    "ArrowDown" | "s" | "S" => game_state_write.update(|game_state| game_state.move_cursor(1, 0)),
+                    "ArrowLeft" | "a" | "A" => game_state_write.update(|game_state| game_state.move_cursor(0, -1)),
+                    "ArrowRight" | "d" | "D" => game_state_write.update(|game_state| game_state.move_cursor(0, 1)),
+                    " " | "Enter" => {
+                        ev.prevent_default();
+                        game_state_write.update(|game_state| game_state.dig_cursor());
+                    }
+                    "f" | "F" => game_state_write.update(|game_state| game_state.flag_cursor()),
+                    _ => {}
+                }
+            });
+
+            // 回放: 导入一段操作序列后, 按 Play/Step 在当前棋盘(同一个种子)上逐步重放
+            let (replay_moves, set_replay_moves) = create_signal(Vec::<Move>::new());
+            let (replay_index, set_replay_index) = create_signal(0usize);
+            let (replay_playing, set_replay_playing) = create_signal(false);
+            let replay_loaded = create_memo(move |_| !replay_moves.with(|moves| moves.is_empty()));
+            let replay_done = create_memo(move |_| replay_index.get() >= replay_moves.with(|moves| moves.len()));
+
+            let step_replay = move || {
+                let index = replay_index.get_untracked();
+                let mv = replay_moves.with_untracked(|moves| moves.get(index).copied());
+
+                match mv {
+                    Some(mv) => {
+                        game_state_write.update(|game_state| game_state.replay_move(mv));
+                        set_replay_index.set(index + 1);
+                    }
+                    None => set_replay_playing.set(false),
+                }
+            };
+
+            let interval_handle = store_value(None::<IntervalHandle>);
+
+            create_effect(move |_| {
+                if replay_playing.get() && !replay_done.get() {
+                    if let Ok(handle) = set_interval_with_handle(step_replay, std::time::Duration::from_millis(400)) {
+                        interval_handle.update_value(|slot| *slot = Some(handle));
+                    }
+                } else {
+                    interval_handle.update_value(|slot| {
+                        if let Some(handle) = slot.take() {
+                            handle.clear();
+                        }
+                    });
+                }
+            });
+
+            // 回放播放/单步完毕后棋盘要恢复正常交互: 清掉 GameState 里的
+            // "重放中" 标记，不然之后真人下的每一步都不会被存档和计入统计
+            create_effect(move |_| {
+                if replay_loaded.get() && replay_done.get() {
+                    game_state_write.update(|game_state| game_state.finish_replay());
+                }
+            });
+
             view! {
                 <div class="btns">
                     <div class=move || { format!("btn {}", if new_game_enabled() { "" } else { "disabled" }) }>
@@ -48,6 +155,12 @@ pub fn Game() -> impl IntoView {
 
                                 if new_game_enabled() {
                                     game_state_write.update(|game_state| game_state.reset());
+
+                                    // reset() 可能换了新 seed, 地址栏也要跟着更新,
+                                    // 否则分享出去的 URL 打开的还是重开之前的旧棋盘
+                                    game_state_read.with_untracked(|game_state| {
+                                        sync_seed_to_url(game_state.size(), game_state.seed());
+                                    });
                                 }
                             }
 
@@ -56,6 +169,88 @@ pub fn Game() -> impl IntoView {
                             "New Game"
                         </A>
                     </div>
+                    <div class="btn">
+                        <A
+                            href=""
+
+                            on:click=move |ev| {
+                                ev.prevent_default();
+                                game_state_write.update(|game_state| game_state.hint());
+                            }
+                        >
+                            "Hint"
+                        </A>
+                    </div>
+                    <div class="btn">
+                        <A
+                            href=""
+
+                            on:click=move |ev| {
+                                ev.prevent_default();
+
+                                if let Ok(json) = game_state_read.with_untracked(|game_state| game_state.export_moves()) {
+                                    let _ = window().alert_with_message(&json);
+                                }
+                            }
+                        >
+                            "Export Replay"
+                        </A>
+                    </div>
+                    <div class="btn">
+                        <A
+                            href=""
+
+                            on:click=move |ev| {
+                                ev.prevent_default();
+
+                                if let Ok(Some(json)) = window().prompt_with_message("粘贴回放 JSON:") {
+                                    if let Ok(moves) = GameState::import_moves(&json) {
+                                        game_state_write.update(|game_state| game_state.start_replay());
+                                        set_replay_moves.set(moves);
+                                        set_replay_index.set(0);
+                                        set_replay_playing.set(false);
+                                    }
+                                }
+                            }
+                        >
+                            "Import Replay"
+                        </A>
+                    </div>
+                    <div class=move || { format!("btn {}", if replay_loaded() { "" } else { "disabled" }) }>
+                        <A
+                            href=""
+
+                            on:click=move |ev| {
+                                ev.prevent_default();
+
+                                if replay_loaded() && !replay_done() {
+                                    set_replay_playing.update(|playing| *playing = !*playing);
+                                }
+                            }
+                        >
+                            { move || if replay_playing() { "Pause" } else { "Play" } }
+                        </A>
+                    </div>
+                    <div class=move || { format!("btn {}", if replay_loaded() && !replay_done() { "" } else { "disabled" }) }>
+                        <A
+                            href=""
+
+                            on:click=move |ev| {
+                                ev.prevent_default();
+
+                                if replay_loaded() && !replay_done() {
+                                    step_replay();
+                                }
+                            }
+                        >
+                            "Step"
+                        </A>
+                    </div>
+                    <div class="btn">
+                        <A href="/stats">
+                            "Stats"
+                        </A>
+                    </div>
                     <div class="btn">
                         <A href="/">
                             "Return"
@@ -119,9 +314,15 @@ fn Row(row: isize, columns: isize) -> impl IntoView {
 fn Cell(row: isize, column: isize) -> impl IntoView {
     let (cell_state, set_cell_state) =
         create_signal((CellInteraction::Untouched, CellKind::Clear(0)));
+    let (highlight, set_highlight) = create_signal(Highlight::None);
+    let (focused, set_focused) = create_signal(false);
     let game_state_write = use_context::<WriteSignal<GameState>>().expect("game state exists");
 
-    game_state_write.update(|game_state| game_state.register_cell(row, column, set_cell_state));
+    game_state_write.update(|game_state| {
+        game_state.register_cell(row, column, set_cell_state);
+        game_state.register_highlight(row, column, set_highlight);
+        game_state.register_focus(row, column, set_focused);
+    });
 
     view! {
         <div
@@ -130,6 +331,9 @@ fn Cell(row: isize, column: isize) -> impl IntoView {
                     0 => { // 左键点击, 挖掘
                         game_state_write.update(|game_state| game_state.dig(row, column));
                     }
+                    1 => { // 中键点击, 连锁挖掘(chord)已满足数字的格子
+                        game_state_write.update(|game_state| game_state.chord(row, column));
+                    }
                     2 => { // 右键点击, 插旗
                         game_state_write.update(|game_state| game_state.flag(row, column));
                     }
@@ -149,6 +353,10 @@ fn Cell(row: isize, column: isize) -> impl IntoView {
                 matches!(cell_state().0, CellInteraction::Cleared)
             }
 
+            class:hint-safe=move || matches!(highlight(), Highlight::Safe)
+            class:hint-mine=move || matches!(highlight(), Highlight::Mine)
+            class:focused=move || focused()
+
             style:grid-row-start={row+1}
             style:grid-column-start={column+1}
 
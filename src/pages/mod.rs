@@ -0,0 +1,23 @@
+mod game;
+mod stats;
+
+pub use game::Game;
+pub use stats::StatsPage;
+
+use leptos::*;
+
+/// 展示捕获到的错误信息
+#[component]
+pub fn Error(outside_errors: Errors) -> impl IntoView {
+    let errors = outside_errors
+        .into_iter()
+        .map(|(_, error)| error.to_string())
+        .collect::<Vec<_>>();
+
+    view! {
+        <div class="error-page">
+            <h1>"Error"</h1>
+            { errors.into_iter().map(|error| view! { <p>{error}</p> }).collect_view() }
+        </div>
+    }
+}
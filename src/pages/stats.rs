@@ -0,0 +1,61 @@
+use leptos::*;
+use leptos_router::*;
+
+use crate::game_settings::Size;
+use crate::game_stats::Stats;
+
+const DIFFICULTIES: [Size; 3] = [Size::Small, Size::Medium, Size::Large];
+
+// 渲染各难度档位的统计面板
+#[component]
+pub fn StatsPage() -> impl IntoView {
+    let stats = Stats::load();
+
+    view! {
+        <div class="stats-page">
+            <h1>"Stats"</h1>
+            <table class="stats-table">
+                <thead>
+                    <tr>
+                        <th>"Difficulty"</th>
+                        <th>"Played"</th>
+                        <th>"Won"</th>
+                        <th>"Win rate"</th>
+                        <th>"Streak"</th>
+                        <th>"Longest streak"</th>
+                        <th>"Best time"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { DIFFICULTIES.map(|size| {
+                        let difficulty_stats = stats.for_difficulty(size);
+
+                        let best_time = match difficulty_stats.best_time_secs {
+                            Some(secs) => format!("{secs}s"),
+                            None => "-".to_string(),
+                        };
+
+                        view! {
+                            <tr>
+                                <td>{ size.to_string() }</td>
+                                <td>{ difficulty_stats.games_played }</td>
+                                <td>{ difficulty_stats.games_won }</td>
+                                <td>{ format!("{:.0}%", difficulty_stats.win_rate() * 100.0) }</td>
+                                <td>{ difficulty_stats.current_streak }</td>
+                                <td>{ difficulty_stats.longest_streak }</td>
+                                <td>{ best_time }</td>
+                            </tr>
+                        }
+                    }).collect_view() }
+                </tbody>
+            </table>
+            <div class="btns">
+                <div class="btn">
+                    <A href="/">
+                        "Return"
+                    </A>
+                </div>
+            </div>
+        </div>
+    }
+}